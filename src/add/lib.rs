@@ -8,18 +8,12 @@ use halo2_proofs::{
     poly::Rotation,
 };
 
-/// Interface for the AddInstruction.
-pub(crate) trait AddInstructions<F: FieldExt>: Chip<F> {
-    /// Variable representing a number.
-    type Num;
+use crate::utilities::lib::Var;
 
+/// Interface for the AddInstruction.
+pub(crate) trait AddInstructions<F: FieldExt, V: Var<F>>: Chip<F> {
     /// Returns `c = a + b`.
-    fn add(
-        &self,
-        layouter: impl Layouter<F>,
-        a: Self::Num,
-        b: Self::Num,
-    ) -> Result<Self::Num, Error>;
+    fn add(&self, layouter: impl Layouter<F>, a: V, b: V) -> Result<V, Error>;
 }
 
 /// Config for the add chip.
@@ -28,7 +22,7 @@ pub(crate) struct AddConfig {
     /// One advice column for the instruction.
     advice: Column<Advice>,
     /// Selector for the add instruction.
-    s_add: Selector,
+    pub(crate) s_add: Selector,
 }
 
 /// A chip for the add functionality.
@@ -90,15 +84,8 @@ impl<F: FieldExt> AddChip<F> {
     }
 }
 
-impl<F: FieldExt> AddInstructions<F> for AddChip<F> {
-    type Num = crate::Number<F>;
-
-    fn add(
-        &self,
-        mut layouter: impl Layouter<F>,
-        a: Self::Num,
-        b: Self::Num,
-    ) -> Result<Self::Num, Error> {
+impl<F: FieldExt, V: Var<F>> AddInstructions<F, V> for AddChip<F> {
+    fn add(&self, mut layouter: impl Layouter<F>, a: V, b: V) -> Result<V, Error> {
         let config = self.config();
 
         layouter.assign_region(
@@ -106,14 +93,17 @@ impl<F: FieldExt> AddInstructions<F> for AddChip<F> {
             |mut region: Region<'_, F>| {
                 config.s_add.enable(&mut region, 0)?;
 
-                a.0.copy_advice(|| "lhs", &mut region, config.advice, 0)?;
-                b.0.copy_advice(|| "rhs", &mut region, config.advice, 1)?;
+                let lhs = region.assign_advice(|| "lhs", config.advice, 0, || a.value())?;
+                region.constrain_equal(a.cell(), lhs.cell())?;
+
+                let rhs = region.assign_advice(|| "rhs", config.advice, 1, || b.value())?;
+                region.constrain_equal(b.cell(), rhs.cell())?;
 
-                let value = a.0.value().copied() + b.0.value().copied();
+                let value = a.value() + b.value();
 
                 region
                     .assign_advice(|| "lhs + rhs", config.advice, 2, || value)
-                    .map(crate::Number)
+                    .map(V::from)
             },
         )
     }
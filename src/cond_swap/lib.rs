@@ -0,0 +1,166 @@
+/// Constructs the conditional-swap chip.
+use std::marker::PhantomData;
+
+use halo2_proofs::{
+    arithmetic::FieldExt,
+    circuit::{Chip, Layouter, Region, Value},
+    plonk::{Advice, Column, ConstraintSystem, Error, Expression, Selector},
+    poly::Rotation,
+};
+
+use crate::utilities::lib::Var;
+
+/// Interface for the CondSwapInstruction.
+pub(crate) trait CondSwapInstructions<F: FieldExt, V: Var<F>>: Chip<F> {
+    /// Returns `(a, b)` unchanged when `swap == 0`, or `(b, a)` when
+    /// `swap == 1`.
+    fn swap(
+        &self,
+        layouter: impl Layouter<F>,
+        a: V,
+        b: V,
+        swap: Value<bool>,
+    ) -> Result<(V, V), Error>;
+}
+
+/// Config for the conditional-swap chip.
+#[derive(Clone, Debug)]
+pub(crate) struct CondSwapConfig {
+    /// Advice column holding `a`.
+    a: Column<Advice>,
+    /// Advice column holding `b`.
+    b: Column<Advice>,
+    /// Advice column holding the witnessed boolean swap flag.
+    swap: Column<Advice>,
+    /// Advice column holding `a` or `b`, depending on `swap`.
+    a_swapped: Column<Advice>,
+    /// Advice column holding `b` or `a`, depending on `swap`.
+    b_swapped: Column<Advice>,
+    /// Selector for the conditional-swap gate.
+    s_swap: Selector,
+}
+
+/// A chip for the conditional-swap functionality.
+pub(crate) struct CondSwapChip<F: FieldExt> {
+    config: CondSwapConfig,
+    _marker: PhantomData<F>,
+}
+
+// Implementations for the conditional-swap chip below.
+
+impl<F: FieldExt> Chip<F> for CondSwapChip<F> {
+    type Config = CondSwapConfig;
+    type Loaded = ();
+
+    fn config(&self) -> &Self::Config {
+        &self.config
+    }
+
+    fn loaded(&self) -> &Self::Loaded {
+        &()
+    }
+}
+
+impl<F: FieldExt> CondSwapChip<F> {
+    pub(crate) fn construct(config: <Self as Chip<F>>::Config) -> Self {
+        Self {
+            config,
+            _marker: PhantomData,
+        }
+    }
+
+    pub(crate) fn configure(
+        meta: &mut ConstraintSystem<F>,
+        a: Column<Advice>,
+        b: Column<Advice>,
+        swap: Column<Advice>,
+        a_swapped: Column<Advice>,
+        b_swapped: Column<Advice>,
+    ) -> <Self as Chip<F>>::Config {
+        meta.enable_equality(a);
+        meta.enable_equality(b);
+        meta.enable_equality(a_swapped);
+        meta.enable_equality(b_swapped);
+
+        let s_swap = meta.selector();
+
+        // Define our conditional-swap gate.
+        meta.create_gate("cond_swap", |meta| {
+            let a = meta.query_advice(a, Rotation::cur());
+            let b = meta.query_advice(b, Rotation::cur());
+            let swap = meta.query_advice(swap, Rotation::cur());
+            let a_swapped = meta.query_advice(a_swapped, Rotation::cur());
+            let b_swapped = meta.query_advice(b_swapped, Rotation::cur());
+            let s_swap = meta.query_selector(s_swap);
+
+            let one = Expression::Constant(F::one());
+
+            // `swap` must be boolean.
+            let bool_check = swap.clone() * (one - swap.clone());
+
+            // `a_swapped = swap * (b - a) + a`, i.e. `(b - a) * swap - (a_swapped - a) = 0`.
+            let a_check = (b.clone() - a.clone()) * swap.clone() - (a_swapped - a.clone());
+
+            // `b_swapped = swap * (a - b) + b`, i.e. `(a - b) * swap - (b_swapped - b) = 0`.
+            let b_check = (a - b.clone()) * swap - (b_swapped - b);
+
+            vec![
+                s_swap.clone() * bool_check,
+                s_swap.clone() * a_check,
+                s_swap * b_check,
+            ]
+        });
+
+        CondSwapConfig {
+            a,
+            b,
+            swap,
+            a_swapped,
+            b_swapped,
+            s_swap,
+        }
+    }
+}
+
+impl<F: FieldExt, V: Var<F>> CondSwapInstructions<F, V> for CondSwapChip<F> {
+    fn swap(
+        &self,
+        mut layouter: impl Layouter<F>,
+        a: V,
+        b: V,
+        swap: Value<bool>,
+    ) -> Result<(V, V), Error> {
+        let config = self.config();
+
+        layouter.assign_region(
+            || "cond_swap",
+            |mut region: Region<'_, F>| {
+                config.s_swap.enable(&mut region, 0)?;
+
+                let a_cell = region.assign_advice(|| "a", config.a, 0, || a.value())?;
+                region.constrain_equal(a.cell(), a_cell.cell())?;
+
+                let b_cell = region.assign_advice(|| "b", config.b, 0, || b.value())?;
+                region.constrain_equal(b.cell(), b_cell.cell())?;
+
+                let swap_value = swap.map(|swap| F::from(swap as u64));
+                region.assign_advice(|| "swap", config.swap, 0, || swap_value)?;
+
+                let (a_swapped_value, b_swapped_value) = swap
+                    .zip(a_cell.value().copied())
+                    .zip(b_cell.value().copied())
+                    .map(|((swap, a), b)| if swap { (b, a) } else { (a, b) })
+                    .unzip();
+
+                let a_swapped = region
+                    .assign_advice(|| "a_swapped", config.a_swapped, 0, || a_swapped_value)
+                    .map(V::from)?;
+                let b_swapped = region
+                    .assign_advice(|| "b_swapped", config.b_swapped, 0, || b_swapped_value)
+                    .map(V::from)?;
+
+                Ok((a_swapped, b_swapped))
+            },
+        )
+    }
+}
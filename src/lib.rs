@@ -2,54 +2,74 @@ use std::marker::PhantomData;
 
 use halo2_proofs::{
     arithmetic::FieldExt,
-    circuit::{AssignedCell, Chip, Layouter, SimpleFloorPlanner, Value},
-    plonk::{Advice, Circuit, Column, ConstraintSystem, Error, Instance},
+    circuit::{AssignedCell, Cell, Chip, Layouter, Region, SimpleFloorPlanner, Value},
+    plonk::{Advice, Circuit, Column, ConstraintSystem, Error, Fixed, Instance},
 };
 
 mod add;
+mod cond_swap;
 mod mul;
+mod poseidon;
+mod sub;
+mod utilities;
 
 use add::lib::{AddChip, AddConfig, AddInstructions};
+use cond_swap::lib::{CondSwapChip, CondSwapConfig, CondSwapInstructions};
 use mul::lib::{MulChip, MulConfig, MulInstructions};
+use poseidon::lib::{PoseidonChip, PoseidonConfig, PoseidonInstructions};
+use sub::lib::{SubChip, SubConfig, SubInstructions};
+use utilities::lib::{UtilitiesInstructions, Var};
 
 /// A variable representing a number.
 #[derive(Clone)]
 struct Number<F: FieldExt>(AssignedCell<F, F>);
 
-trait SolutionInstructions<F: FieldExt>: AddInstructions<F> + MulInstructions<F> {
-    /// Variable representing a number.
-    type Num;
+impl<F: FieldExt> From<AssignedCell<F, F>> for Number<F> {
+    fn from(cell: AssignedCell<F, F>) -> Self {
+        Number(cell)
+    }
+}
 
-    /// Loads a number into the circuit as a private input.
-    fn load_private(
-        &self,
-        layouter: impl Layouter<F>,
-        a: Value<F>,
-    ) -> Result<<Self as SolutionInstructions<F>>::Num, Error>;
+impl<F: FieldExt> Var<F> for Number<F> {
+    fn cell(&self) -> Cell {
+        self.0.cell()
+    }
 
+    fn value(&self) -> Value<F> {
+        self.0.value().copied()
+    }
+}
+
+trait SolutionInstructions<F: FieldExt, V: Var<F>>:
+    UtilitiesInstructions<F, Var = V>
+    + AddInstructions<F, V>
+    + MulInstructions<F, V>
+    + SubInstructions<F, V>
+    + CondSwapInstructions<F, V>
+    + PoseidonInstructions<F, V>
+{
     /// Loads a, b, c into the circuit.
-    fn load_constants(
-        &self,
-        layouter: impl Layouter<F>,
-    ) -> Result<[<Self as SolutionInstructions<F>>::Num; 3], Error>;
+    fn load_constants(&self, layouter: impl Layouter<F>) -> Result<[V; 3], Error>;
+
+    /// Loads a compile-time constant into the circuit, without exposing it
+    /// as a public input.
+    fn load_constant(&self, layouter: impl Layouter<F>, constant: F) -> Result<V, Error>;
 
     /// Exposes a number as a public input to the circuit.
-    fn expose_public(
-        &self,
-        layouter: impl Layouter<F>,
-        num: <Self as SolutionInstructions<F>>::Num,
-        row: usize,
-    ) -> Result<(), Error>;
+    fn expose_public(&self, layouter: impl Layouter<F>, num: V, row: usize) -> Result<(), Error>;
 
     /// Returns a * x^2 + b * x - c.
     fn solve_quadratic(
         &self,
         layouter: &mut impl Layouter<F>,
-        a: <Self as SolutionInstructions<F>>::Num,
-        b: <Self as SolutionInstructions<F>>::Num,
-        c: <Self as SolutionInstructions<F>>::Num,
-        x: <Self as SolutionInstructions<F>>::Num,
-    ) -> Result<<Self as SolutionInstructions<F>>::Num, Error>;
+        a: V,
+        b: V,
+        c: V,
+        x: V,
+    ) -> Result<V, Error>;
+
+    /// Returns `d = (a + b) * c`.
+    fn add_and_mul(&self, layouter: &mut impl Layouter<F>, a: V, b: V, c: V) -> Result<V, Error>;
 }
 
 struct SolutionChip<F: FieldExt> {
@@ -63,10 +83,18 @@ struct SolutionConfig {
     advice: Column<Advice>,
     /// One column for the instance variables (a, b, c).
     instance: Column<Instance>,
+    /// Fixed column used to load compile-time constants.
+    constant: Column<Fixed>,
     /// Config for the `Add` chip.
     add_config: AddConfig,
     /// Config for the `Mul` chip.
     mul_config: MulConfig,
+    /// Config for the `Sub` chip.
+    sub_config: SubConfig,
+    /// Config for the `CondSwap` chip.
+    cond_swap_config: CondSwapConfig,
+    /// Config for the `Poseidon` chip.
+    poseidon_config: PoseidonConfig,
 }
 
 impl<F: FieldExt> Chip<F> for SolutionChip<F> {
@@ -82,36 +110,56 @@ impl<F: FieldExt> Chip<F> for SolutionChip<F> {
     }
 }
 
-impl<F: FieldExt> AddInstructions<F> for SolutionChip<F> {
-    type Num = Number<F>;
-
-    fn add(
-        &self,
-        layouter: impl Layouter<F>,
-        a: Self::Num,
-        b: Self::Num,
-    ) -> Result<Self::Num, Error> {
+impl<F: FieldExt, V: Var<F>> AddInstructions<F, V> for SolutionChip<F> {
+    fn add(&self, layouter: impl Layouter<F>, a: V, b: V) -> Result<V, Error> {
         let config = self.config().add_config.clone();
         let add_chip = AddChip::<F>::construct(config);
         add_chip.add(layouter, a, b)
     }
 }
 
-impl<F: FieldExt> MulInstructions<F> for SolutionChip<F> {
-    type Num = Number<F>;
-
-    fn mul(
-        &self,
-        layouter: impl Layouter<F>,
-        a: Self::Num,
-        b: Self::Num,
-    ) -> Result<Self::Num, Error> {
+impl<F: FieldExt, V: Var<F>> MulInstructions<F, V> for SolutionChip<F> {
+    fn mul(&self, layouter: impl Layouter<F>, a: V, b: V) -> Result<V, Error> {
         let config = self.config().mul_config.clone();
         let mul_chip = MulChip::<F>::construct(config);
         mul_chip.mul(layouter, a, b)
     }
 }
 
+impl<F: FieldExt, V: Var<F>> SubInstructions<F, V> for SolutionChip<F> {
+    fn sub(&self, layouter: impl Layouter<F>, a: V, b: V) -> Result<V, Error> {
+        let config = self.config().sub_config.clone();
+        let sub_chip = SubChip::<F>::construct(config);
+        sub_chip.sub(layouter, a, b)
+    }
+}
+
+impl<F: FieldExt, V: Var<F>> CondSwapInstructions<F, V> for SolutionChip<F> {
+    fn swap(
+        &self,
+        layouter: impl Layouter<F>,
+        a: V,
+        b: V,
+        swap: Value<bool>,
+    ) -> Result<(V, V), Error> {
+        let config = self.config().cond_swap_config.clone();
+        let cond_swap_chip = CondSwapChip::<F>::construct(config);
+        cond_swap_chip.swap(layouter, a, b, swap)
+    }
+}
+
+impl<F: FieldExt, V: Var<F>> PoseidonInstructions<F, V> for SolutionChip<F> {
+    fn hash(&self, layouter: impl Layouter<F>, inputs: &[V]) -> Result<V, Error> {
+        let config = self.config().poseidon_config.clone();
+        let poseidon_chip = PoseidonChip::<F>::construct(config);
+        poseidon_chip.hash(layouter, inputs)
+    }
+}
+
+impl<F: FieldExt> UtilitiesInstructions<F> for SolutionChip<F> {
+    type Var = Number<F>;
+}
+
 impl<F: FieldExt> SolutionChip<F> {
     fn construct(config: <Self as Chip<F>>::Config) -> Self {
         Self {
@@ -127,41 +175,40 @@ impl<F: FieldExt> SolutionChip<F> {
     ) -> <Self as Chip<F>>::Config {
         let add_config = AddChip::configure(meta, advice);
         let mul_config = MulChip::configure(meta, advice);
+        let sub_config = SubChip::configure(meta, advice);
         meta.enable_equality(instance);
 
+        let constant = meta.fixed_column();
+        meta.enable_constant(constant);
+
+        let a = meta.advice_column();
+        let b = meta.advice_column();
+        let swap = meta.advice_column();
+        let a_swapped = meta.advice_column();
+        let b_swapped = meta.advice_column();
+        let cond_swap_config =
+            CondSwapChip::configure(meta, a, b, swap, a_swapped, b_swapped);
+
+        let state = [meta.advice_column(), meta.advice_column(), meta.advice_column()];
+        let x2 = [meta.advice_column(), meta.advice_column(), meta.advice_column()];
+        let x4 = [meta.advice_column(), meta.advice_column(), meta.advice_column()];
+        let poseidon_config = PoseidonChip::configure(meta, state, x2, x4);
+
         SolutionConfig {
             add_config,
             mul_config,
+            sub_config,
+            cond_swap_config,
+            poseidon_config,
             advice,
             instance,
+            constant,
         }
     }
 }
 
-impl<F: FieldExt> SolutionInstructions<F> for SolutionChip<F> {
-    type Num = crate::Number<F>;
-
-    fn load_private(
-        &self,
-        mut layouter: impl Layouter<F>,
-        value: Value<F>,
-    ) -> Result<<Self as SolutionInstructions<F>>::Num, Error> {
-        let config = self.config();
-
-        layouter.assign_region(
-            || "load private",
-            |mut region| {
-                region
-                    .assign_advice(|| "private input", config.advice, 0, || value)
-                    .map(Number)
-            },
-        )
-    }
-
-    fn load_constants(
-        &self,
-        mut layouter: impl Layouter<F>,
-    ) -> Result<[<Self as SolutionInstructions<F>>::Num; 3], Error> {
+impl<F: FieldExt> SolutionInstructions<F, Number<F>> for SolutionChip<F> {
+    fn load_constants(&self, mut layouter: impl Layouter<F>) -> Result<[Number<F>; 3], Error> {
         let config = self.config();
 
         layouter.assign_region(
@@ -182,10 +229,27 @@ impl<F: FieldExt> SolutionInstructions<F> for SolutionChip<F> {
         )
     }
 
+    fn load_constant(
+        &self,
+        mut layouter: impl Layouter<F>,
+        constant: F,
+    ) -> Result<Number<F>, Error> {
+        let config = self.config();
+
+        layouter.assign_region(
+            || "load constant",
+            |mut region| {
+                region
+                    .assign_advice_from_constant(|| "constant value", config.advice, 0, constant)
+                    .map(Number)
+            },
+        )
+    }
+
     fn expose_public(
         &self,
         mut layouter: impl Layouter<F>,
-        num: <Self as SolutionInstructions<F>>::Num,
+        num: Number<F>,
         row: usize,
     ) -> Result<(), Error> {
         let config = self.config();
@@ -196,15 +260,67 @@ impl<F: FieldExt> SolutionInstructions<F> for SolutionChip<F> {
     fn solve_quadratic(
         &self,
         layouter: &mut impl Layouter<F>,
-        a: <Self as SolutionInstructions<F>>::Num,
-        b: <Self as SolutionInstructions<F>>::Num,
-        _c: <Self as SolutionInstructions<F>>::Num,
-        x: <Self as SolutionInstructions<F>>::Num,
-    ) -> Result<<Self as SolutionInstructions<F>>::Num, Error> {
+        a: Number<F>,
+        b: Number<F>,
+        c: Number<F>,
+        x: Number<F>,
+    ) -> Result<Number<F>, Error> {
         let x2 = self.mul(layouter.namespace(|| "x2"), x.clone(), x.clone())?;
         let bx = self.mul(layouter.namespace(|| "bx"), b, x)?;
         let ax2 = self.mul(layouter.namespace(|| "ax2"), a, x2)?;
-        self.add(layouter.namespace(|| "ax2 + bx"), ax2, bx)
+        let ax2_plus_bx = self.add(layouter.namespace(|| "ax2 + bx"), ax2, bx)?;
+        self.sub(layouter.namespace(|| "ax2 + bx - c"), ax2_plus_bx, c)
+    }
+
+    fn add_and_mul(
+        &self,
+        layouter: &mut impl Layouter<F>,
+        a: Number<F>,
+        b: Number<F>,
+        c: Number<F>,
+    ) -> Result<Number<F>, Error> {
+        let config = self.config();
+        let advice = config.advice;
+        let s_add = config.add_config.s_add;
+        let s_mul = config.mul_config.s_mul;
+
+        layouter.namespace(|| "add_and_mul").assign_region(
+            || "(a + b) * c",
+            |mut region: Region<'_, F>| {
+                s_add.enable(&mut region, 0)?;
+
+                let lhs = region.assign_advice(|| "lhs", advice, 0, || a.value())?;
+                region.constrain_equal(a.cell(), lhs.cell())?;
+                let rhs = region.assign_advice(|| "rhs", advice, 1, || b.value())?;
+                region.constrain_equal(b.cell(), rhs.cell())?;
+                let sum = region.assign_advice(
+                    || "lhs + rhs",
+                    advice,
+                    2,
+                    || lhs.value().copied() + rhs.value().copied(),
+                )?;
+
+                s_mul.enable(&mut region, 3)?;
+
+                // The add output cell is reused directly as the mul chip's
+                // left operand: a single `copy_advice` into the next row of
+                // the same shared advice column, rather than a second
+                // assign_advice + constrain_equal round trip through a
+                // separate `mul` region.
+                let mul_lhs = sum.copy_advice(|| "lhs", &mut region, advice, 3)?;
+                let mul_rhs = region.assign_advice(|| "rhs", advice, 4, || c.value())?;
+                region.constrain_equal(c.cell(), mul_rhs.cell())?;
+
+                region
+                    .assign_advice(
+                        || "lhs * rhs",
+                        advice,
+                        5,
+                        || mul_lhs.value().copied() * mul_rhs.value().copied(),
+                    )
+                    .map(Number)
+            },
+        )
     }
 }
 
@@ -235,20 +351,23 @@ impl<F: FieldExt> Circuit<F> for MyCircuit<F> {
         mut layouter: impl Layouter<F>,
     ) -> Result<(), Error> {
         let solution_chip = SolutionChip::<F>::construct(config);
+        let advice = solution_chip.config().advice;
 
-        let x = solution_chip.load_private(layouter.namespace(|| "load x"), self.x)?;
+        let x = solution_chip.load_private(layouter.namespace(|| "load x"), advice, self.x)?;
         let [a, b, c] = solution_chip.load_constants(layouter.namespace(|| "load a,b,c"))?;
 
         let solution = solution_chip.solve_quadratic(&mut layouter, a, b, c, x)?;
 
-        solution_chip.expose_public(layouter.namespace(|| "expose solution"), solution, 2)
+        solution_chip.expose_public(layouter.namespace(|| "expose solution"), solution, 3)
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::MyCircuit;
-    use halo2_proofs::{circuit::Value, dev::MockProver, pasta::Fp};
+    use halo2_proofs::{
+        arithmetic::FieldExt, circuit::Value, dev::MockProver, pasta::Fp, plonk::Error,
+    };
 
     #[test]
     fn test_solve_quad() {
@@ -257,10 +376,12 @@ mod tests {
         let b = Fp::from(2);
         let c = Fp::from(3);
         let x = Fp::from(1);
+        // a * x^2 + b * x - c = 1 + 2 - 3 = 0
+        let solution = Fp::from(0);
 
         let circuit = MyCircuit { x: Value::known(x) };
 
-        let prover = MockProver::run(k, &circuit, vec![vec![a, b, c]]).unwrap();
+        let prover = MockProver::run(k, &circuit, vec![vec![a, b, c, solution]]).unwrap();
         assert_eq!(prover.verify(), Ok(()));
     }
 
@@ -271,10 +392,370 @@ mod tests {
         let b = Fp::from(1);
         let c = Fp::from(3);
         let x = Fp::from(1);
+        let wrong_solution = Fp::from(0);
 
         let circuit = MyCircuit { x: Value::known(x) };
 
-        let prover = MockProver::run(k, &circuit, vec![vec![a, b, c]]).unwrap();
+        let prover =
+            MockProver::run(k, &circuit, vec![vec![a, b, c, wrong_solution]]).unwrap();
         assert!(prover.verify().is_err());
     }
+
+    #[test]
+    fn test_load_constant() {
+        use super::{SolutionChip, SolutionInstructions, UtilitiesInstructions};
+        use halo2_proofs::{
+            circuit::{Chip, SimpleFloorPlanner},
+            plonk::{Circuit, ConstraintSystem},
+        };
+
+        // Mixes an instance-sourced coefficient `a` with a hardcoded
+        // constant `k`, asserting `a + k` as the public output.
+        struct ConstantCircuit<F: FieldExt> {
+            a: Value<F>,
+        }
+
+        impl<F: FieldExt> Default for ConstantCircuit<F> {
+            fn default() -> Self {
+                Self { a: Value::unknown() }
+            }
+        }
+
+        impl<F: FieldExt> Circuit<F> for ConstantCircuit<F> {
+            type Config = super::SolutionConfig;
+            type FloorPlanner = SimpleFloorPlanner;
+
+            fn without_witnesses(&self) -> Self {
+                Self::default()
+            }
+
+            fn configure(meta: &mut ConstraintSystem<F>) -> Self::Config {
+                let advice = meta.advice_column();
+                let instance = meta.instance_column();
+
+                SolutionChip::configure(meta, advice, instance)
+            }
+
+            fn synthesize(
+                &self,
+                config: Self::Config,
+                mut layouter: impl Layouter<F>,
+            ) -> Result<(), Error> {
+                let chip = SolutionChip::<F>::construct(config);
+                let advice = chip.config().advice;
+
+                let a = chip.load_private(layouter.namespace(|| "load a"), advice, self.a)?;
+                chip.expose_public(layouter.namespace(|| "bind a to instance"), a.clone(), 0)?;
+                let k = chip.load_constant(layouter.namespace(|| "load k"), F::from(7))?;
+                let sum = chip.add(layouter.namespace(|| "a + k"), a, k)?;
+
+                chip.expose_public(layouter.namespace(|| "expose a + k"), sum, 1)
+            }
+        }
+
+        let k = 5;
+        let a = Fp::from(3);
+        let expected = Fp::from(10);
+
+        let circuit = ConstantCircuit { a: Value::known(a) };
+
+        let prover = MockProver::run(k, &circuit, vec![vec![a, expected]]).unwrap();
+        assert_eq!(prover.verify(), Ok(()));
+    }
+
+    #[test]
+    fn test_cond_swap() {
+        use super::{SolutionChip, SolutionInstructions, UtilitiesInstructions};
+        use halo2_proofs::{
+            circuit::{Chip, SimpleFloorPlanner},
+            plonk::{Circuit, ConstraintSystem},
+        };
+
+        // Exposes `(a, b)` or `(b, a)`, depending on `swap`, as the two
+        // public outputs.
+        struct CondSwapCircuit<F: FieldExt> {
+            a: Value<F>,
+            b: Value<F>,
+            swap: Value<bool>,
+        }
+
+        impl<F: FieldExt> Default for CondSwapCircuit<F> {
+            fn default() -> Self {
+                Self {
+                    a: Value::unknown(),
+                    b: Value::unknown(),
+                    swap: Value::unknown(),
+                }
+            }
+        }
+
+        impl<F: FieldExt> Circuit<F> for CondSwapCircuit<F> {
+            type Config = super::SolutionConfig;
+            type FloorPlanner = SimpleFloorPlanner;
+
+            fn without_witnesses(&self) -> Self {
+                Self::default()
+            }
+
+            fn configure(meta: &mut ConstraintSystem<F>) -> Self::Config {
+                let advice = meta.advice_column();
+                let instance = meta.instance_column();
+
+                SolutionChip::configure(meta, advice, instance)
+            }
+
+            fn synthesize(
+                &self,
+                config: Self::Config,
+                mut layouter: impl Layouter<F>,
+            ) -> Result<(), Error> {
+                let chip = SolutionChip::<F>::construct(config);
+                let advice = chip.config().advice;
+
+                let a = chip.load_private(layouter.namespace(|| "load a"), advice, self.a)?;
+                let b = chip.load_private(layouter.namespace(|| "load b"), advice, self.b)?;
+                let (a_swapped, b_swapped) =
+                    chip.swap(layouter.namespace(|| "swap"), a, b, self.swap)?;
+
+                chip.expose_public(layouter.namespace(|| "expose a_swapped"), a_swapped, 0)?;
+                chip.expose_public(layouter.namespace(|| "expose b_swapped"), b_swapped, 1)
+            }
+        }
+
+        let k = 5;
+        let a = Fp::from(2);
+        let b = Fp::from(9);
+
+        let no_swap = CondSwapCircuit {
+            a: Value::known(a),
+            b: Value::known(b),
+            swap: Value::known(false),
+        };
+        let prover = MockProver::run(k, &no_swap, vec![vec![a, b]]).unwrap();
+        assert_eq!(prover.verify(), Ok(()));
+
+        let swapped = CondSwapCircuit {
+            a: Value::known(a),
+            b: Value::known(b),
+            swap: Value::known(true),
+        };
+        let prover = MockProver::run(k, &swapped, vec![vec![b, a]]).unwrap();
+        assert_eq!(prover.verify(), Ok(()));
+    }
+
+    #[test]
+    fn test_add_and_mul() {
+        use super::{SolutionChip, SolutionInstructions, UtilitiesInstructions};
+        use halo2_proofs::{
+            circuit::{Chip, SimpleFloorPlanner},
+            plonk::{Circuit, ConstraintSystem},
+        };
+
+        // Exposes `(a + b) * c` as the public output.
+        struct AddAndMulCircuit<F: FieldExt> {
+            a: Value<F>,
+            b: Value<F>,
+            c: Value<F>,
+        }
+
+        impl<F: FieldExt> Default for AddAndMulCircuit<F> {
+            fn default() -> Self {
+                Self {
+                    a: Value::unknown(),
+                    b: Value::unknown(),
+                    c: Value::unknown(),
+                }
+            }
+        }
+
+        impl<F: FieldExt> Circuit<F> for AddAndMulCircuit<F> {
+            type Config = super::SolutionConfig;
+            type FloorPlanner = SimpleFloorPlanner;
+
+            fn without_witnesses(&self) -> Self {
+                Self::default()
+            }
+
+            fn configure(meta: &mut ConstraintSystem<F>) -> Self::Config {
+                let advice = meta.advice_column();
+                let instance = meta.instance_column();
+
+                SolutionChip::configure(meta, advice, instance)
+            }
+
+            fn synthesize(
+                &self,
+                config: Self::Config,
+                mut layouter: impl Layouter<F>,
+            ) -> Result<(), Error> {
+                let chip = SolutionChip::<F>::construct(config);
+                let advice = chip.config().advice;
+
+                let a = chip.load_private(layouter.namespace(|| "load a"), advice, self.a)?;
+                let b = chip.load_private(layouter.namespace(|| "load b"), advice, self.b)?;
+                let c = chip.load_private(layouter.namespace(|| "load c"), advice, self.c)?;
+
+                let d = chip.add_and_mul(&mut layouter, a, b, c)?;
+
+                chip.expose_public(layouter.namespace(|| "expose d"), d, 0)
+            }
+        }
+
+        let k = 5;
+        let a = Fp::from(2);
+        let b = Fp::from(3);
+        let c = Fp::from(4);
+        // (a + b) * c = (2 + 3) * 4 = 20
+        let expected = Fp::from(20);
+
+        let circuit = AddAndMulCircuit {
+            a: Value::known(a),
+            b: Value::known(b),
+            c: Value::known(c),
+        };
+
+        let prover = MockProver::run(k, &circuit, vec![vec![expected]]).unwrap();
+        assert_eq!(prover.verify(), Ok(()));
+    }
+
+    #[test]
+    fn test_poseidon_hash() {
+        use super::{SolutionChip, SolutionInstructions, UtilitiesInstructions};
+        use halo2_proofs::{
+            circuit::{Chip, SimpleFloorPlanner},
+            plonk::{Circuit, ConstraintSystem},
+        };
+
+        // Mirrors the chip's permutation in software: overwrite the rate
+        // lanes with each absorbed chunk, then apply the same round
+        // constants and MDS matrix as `PoseidonChip::configure`.
+        fn poseidon_permute(mut state: [Fp; 3]) -> [Fp; 3] {
+            const R_F: usize = 8;
+            const R_P: usize = 56;
+            const WIDTH: usize = 3;
+            let mds = [
+                [Fp::from(2), Fp::from(1), Fp::from(1)],
+                [Fp::from(1), Fp::from(2), Fp::from(1)],
+                [Fp::from(1), Fp::from(1), Fp::from(3)],
+            ];
+            let full_rounds = R_F / 2;
+
+            for round in 0..R_F + R_P {
+                let is_full = round < full_rounds || round >= full_rounds + R_P;
+
+                let mut sbox_out = [Fp::zero(); WIDTH];
+                for (lane, sbox_out) in sbox_out.iter_mut().enumerate() {
+                    let rc = Fp::from((round * WIDTH + lane + 1) as u64);
+                    let x = state[lane] + rc;
+                    *sbox_out = if is_full || lane == 0 {
+                        x * x * x * x * x
+                    } else {
+                        x
+                    };
+                }
+
+                let mut next = [Fp::zero(); WIDTH];
+                for (j, row) in mds.iter().enumerate() {
+                    for (lane, coeff) in row.iter().enumerate() {
+                        next[j] += *coeff * sbox_out[lane];
+                    }
+                }
+                state = next;
+            }
+
+            state
+        }
+
+        fn poseidon_hash(inputs: &[Fp]) -> Fp {
+            let mut state = [Fp::zero(); 3];
+            for chunk in inputs.chunks(2) {
+                for (lane, input) in chunk.iter().enumerate() {
+                    state[lane] = *input;
+                }
+                state = poseidon_permute(state);
+            }
+            state[0]
+        }
+
+        // Exposes `hash(inputs)` as the public output. `inputs` may span
+        // more than one `RATE`-sized chunk, exercising the sponge's
+        // multi-permutation absorb-and-carry path.
+        struct PoseidonCircuit<F: FieldExt> {
+            inputs: Vec<Value<F>>,
+        }
+
+        impl<F: FieldExt> Default for PoseidonCircuit<F> {
+            fn default() -> Self {
+                Self { inputs: vec![] }
+            }
+        }
+
+        impl<F: FieldExt> Circuit<F> for PoseidonCircuit<F> {
+            type Config = super::SolutionConfig;
+            type FloorPlanner = SimpleFloorPlanner;
+
+            fn without_witnesses(&self) -> Self {
+                Self::default()
+            }
+
+            fn configure(meta: &mut ConstraintSystem<F>) -> Self::Config {
+                let advice = meta.advice_column();
+                let instance = meta.instance_column();
+
+                SolutionChip::configure(meta, advice, instance)
+            }
+
+            fn synthesize(
+                &self,
+                config: Self::Config,
+                mut layouter: impl Layouter<F>,
+            ) -> Result<(), Error> {
+                let chip = SolutionChip::<F>::construct(config);
+                let advice = chip.config().advice;
+
+                let inputs = self
+                    .inputs
+                    .iter()
+                    .enumerate()
+                    .map(|(i, input)| {
+                        chip.load_private(
+                            layouter.namespace(|| format!("load input {i}")),
+                            advice,
+                            *input,
+                        )
+                    })
+                    .collect::<Result<Vec<_>, Error>>()?;
+                let digest = chip.hash(layouter.namespace(|| "hash"), &inputs)?;
+
+                chip.expose_public(layouter.namespace(|| "expose digest"), digest, 0)
+            }
+        }
+
+        let k = 7;
+        let inputs = vec![Fp::from(2), Fp::from(3)];
+        let expected = poseidon_hash(&inputs);
+
+        let circuit = PoseidonCircuit {
+            inputs: inputs.iter().copied().map(Value::known).collect(),
+        };
+
+        let prover = MockProver::run(k, &circuit, vec![vec![expected]]).unwrap();
+        assert_eq!(prover.verify(), Ok(()));
+
+        // Three inputs span two `RATE = 2` chunks: the second chunk only
+        // absorbs one lane, so the other must carry the first permutation's
+        // output forward. Two permutations' worth of rows (plus the carry
+        // row) no longer fit in the single-chunk `k`, so use a larger `k`
+        // for this assertion.
+        let multi_chunk_k = 8;
+        let multi_chunk_inputs = vec![Fp::from(2), Fp::from(3), Fp::from(5)];
+        let expected = poseidon_hash(&multi_chunk_inputs);
+
+        let circuit = PoseidonCircuit {
+            inputs: multi_chunk_inputs.iter().copied().map(Value::known).collect(),
+        };
+
+        let prover = MockProver::run(multi_chunk_k, &circuit, vec![vec![expected]]).unwrap();
+        assert_eq!(prover.verify(), Ok(()));
+    }
 }
@@ -8,17 +8,11 @@ use halo2_proofs::{
     poly::Rotation,
 };
 
-pub(crate) trait MulInstructions<F: FieldExt>: Chip<F> {
-    /// Variable representing a number.
-    type Num;
+use crate::utilities::lib::Var;
 
+pub(crate) trait MulInstructions<F: FieldExt, V: Var<F>>: Chip<F> {
     /// Returns `c = a * b`.
-    fn mul(
-        &self,
-        layouter: impl Layouter<F>,
-        a: Self::Num,
-        b: Self::Num,
-    ) -> Result<Self::Num, Error>;
+    fn mul(&self, layouter: impl Layouter<F>, a: V, b: V) -> Result<V, Error>;
 }
 
 pub(crate) struct MulChip<F: FieldExt> {
@@ -31,7 +25,7 @@ pub(crate) struct MulConfig {
     /// Two advice columns for the instruction.
     advice: Column<Advice>,
     /// Selector for the multiply instruction.
-    s_mul: Selector,
+    pub(crate) s_mul: Selector,
 }
 
 impl<F: FieldExt> Chip<F> for MulChip<F> {
@@ -84,29 +78,26 @@ impl<F: FieldExt> MulChip<F> {
     }
 }
 
-impl<F: FieldExt> MulInstructions<F> for MulChip<F> {
-    type Num = crate::Number<F>;
-
-    fn mul(
-        &self,
-        mut layouter: impl Layouter<F>,
-        a: Self::Num,
-        b: Self::Num,
-    ) -> Result<Self::Num, Error> {
+impl<F: FieldExt, V: Var<F>> MulInstructions<F, V> for MulChip<F> {
+    fn mul(&self, mut layouter: impl Layouter<F>, a: V, b: V) -> Result<V, Error> {
         let config = self.config();
 
         layouter.assign_region(
             || "mul",
             |mut region: Region<'_, F>| {
                 config.s_mul.enable(&mut region, 0)?;
-                a.0.copy_advice(|| "lhs", &mut region, config.advice, 0)?;
-                b.0.copy_advice(|| "rhs", &mut region, config.advice, 1)?;
 
-                let value = a.0.value().copied() * b.0.value().copied();
+                let lhs = region.assign_advice(|| "lhs", config.advice, 0, || a.value())?;
+                region.constrain_equal(a.cell(), lhs.cell())?;
+
+                let rhs = region.assign_advice(|| "rhs", config.advice, 1, || b.value())?;
+                region.constrain_equal(b.cell(), rhs.cell())?;
+
+                let value = a.value() * b.value();
 
                 region
                     .assign_advice(|| "lhs * rhs", config.advice, 2, || value)
-                    .map(crate::Number)
+                    .map(V::from)
             },
         )
     }
@@ -0,0 +1,394 @@
+/// Constructs the Poseidon chip: a sponge-based hash built from a width-3
+/// (rate 2, capacity 1) permutation.
+use std::marker::PhantomData;
+
+use halo2_proofs::{
+    arithmetic::FieldExt,
+    circuit::{AssignedCell, Chip, Layouter, Region, Value},
+    plonk::{Advice, Column, ConstraintSystem, Error, Expression, Fixed, Selector},
+    poly::Rotation,
+};
+
+use crate::utilities::lib::Var;
+
+/// Number of full rounds, split evenly before and after the partial rounds.
+const R_F: usize = 8;
+/// Number of partial rounds.
+const R_P: usize = 56;
+/// Permutation width: two rate lanes plus one capacity lane.
+const WIDTH: usize = 3;
+/// Number of lanes absorbed per permutation call.
+const RATE: usize = 2;
+
+/// Interface for the PoseidonInstruction.
+pub(crate) trait PoseidonInstructions<F: FieldExt, V: Var<F>>: Chip<F> {
+    /// Hashes `inputs` through the sponge, absorbing `RATE` lanes at a time
+    /// and squeezing a single-element digest out of lane 0.
+    fn hash(&self, layouter: impl Layouter<F>, inputs: &[V]) -> Result<V, Error>;
+}
+
+/// Config for the Poseidon chip.
+#[derive(Clone, Debug)]
+pub(crate) struct PoseidonConfig {
+    /// Advice columns holding the three permutation lanes.
+    state: [Column<Advice>; WIDTH],
+    /// Per-lane `x^2` intermediate cell, used to keep the S-box gate at
+    /// degree 2.
+    x2: [Column<Advice>; WIDTH],
+    /// Per-lane `x^4` intermediate cell.
+    x4: [Column<Advice>; WIDTH],
+    /// Fixed columns holding the per-round constants, one per lane.
+    round_constants: [Column<Fixed>; WIDTH],
+    /// Fixed columns holding the MDS matrix coefficients, `mds[i][j]`
+    /// mixing sbox output lane `j` into next-round lane `i`.
+    mds: [[Column<Fixed>; WIDTH]; WIDTH],
+    /// Selector for a full round: the S-box is applied to every lane.
+    s_full: Selector,
+    /// Selector for a partial round: the S-box is applied to lane 0 only.
+    s_partial: Selector,
+}
+
+/// A chip for the Poseidon hash functionality.
+pub(crate) struct PoseidonChip<F: FieldExt> {
+    config: PoseidonConfig,
+    _marker: PhantomData<F>,
+}
+
+// Implementations for the Poseidon chip below.
+
+impl<F: FieldExt> Chip<F> for PoseidonChip<F> {
+    type Config = PoseidonConfig;
+    type Loaded = ();
+
+    fn config(&self) -> &Self::Config {
+        &self.config
+    }
+
+    fn loaded(&self) -> &Self::Loaded {
+        &()
+    }
+}
+
+/// Round constants for each of the `R_F + R_P` rounds, one triple per round.
+fn round_constants<F: FieldExt>() -> Vec<[F; WIDTH]> {
+    (0..R_F + R_P)
+        .map(|round| {
+            let mut rc = [F::zero(); WIDTH];
+            for (lane, rc) in rc.iter_mut().enumerate() {
+                *rc = F::from((round * WIDTH + lane + 1) as u64);
+            }
+            rc
+        })
+        .collect()
+}
+
+/// The fixed 3x3 MDS matrix mixing the three permutation lanes after the
+/// S-box layer.
+fn mds<F: FieldExt>() -> [[F; WIDTH]; WIDTH] {
+    [
+        [F::from(2), F::from(1), F::from(1)],
+        [F::from(1), F::from(2), F::from(1)],
+        [F::from(1), F::from(1), F::from(3)],
+    ]
+}
+
+impl<F: FieldExt> PoseidonChip<F> {
+    pub(crate) fn construct(config: <Self as Chip<F>>::Config) -> Self {
+        Self {
+            config,
+            _marker: PhantomData,
+        }
+    }
+
+    pub(crate) fn configure(
+        meta: &mut ConstraintSystem<F>,
+        state: [Column<Advice>; WIDTH],
+        x2: [Column<Advice>; WIDTH],
+        x4: [Column<Advice>; WIDTH],
+    ) -> <Self as Chip<F>>::Config {
+        for column in state {
+            meta.enable_equality(column);
+        }
+
+        let round_constants = [
+            meta.fixed_column(),
+            meta.fixed_column(),
+            meta.fixed_column(),
+        ];
+        let mds: [[Column<Fixed>; WIDTH]; WIDTH] = [
+            [
+                meta.fixed_column(),
+                meta.fixed_column(),
+                meta.fixed_column(),
+            ],
+            [
+                meta.fixed_column(),
+                meta.fixed_column(),
+                meta.fixed_column(),
+            ],
+            [
+                meta.fixed_column(),
+                meta.fixed_column(),
+                meta.fixed_column(),
+            ],
+        ];
+        let s_full = meta.selector();
+        let s_partial = meta.selector();
+
+        // Full round: the S-box `x -> x^5` is applied to every lane, then
+        // the state is mixed through the MDS matrix.
+        meta.create_gate("poseidon full round", |meta| {
+            let s_full = meta.query_selector(s_full);
+
+            let sbox: Vec<_> = (0..WIDTH)
+                .map(|i| {
+                    let x = meta.query_advice(state[i], Rotation::cur())
+                        + meta.query_fixed(round_constants[i], Rotation::cur());
+                    let x2 = meta.query_advice(x2[i], Rotation::cur());
+                    let x4 = meta.query_advice(x4[i], Rotation::cur());
+                    (x, x2, x4)
+                })
+                .collect();
+
+            let mut constraints = Vec::with_capacity(3 * WIDTH);
+            for (x, x2, x4) in sbox.iter() {
+                constraints.push(s_full.clone() * (x2.clone() - x.clone() * x.clone()));
+                constraints.push(s_full.clone() * (x4.clone() - x2.clone() * x2.clone()));
+            }
+
+            let sbox_out: Vec<_> = sbox
+                .iter()
+                .map(|(x, _, x4)| x4.clone() * x.clone())
+                .collect();
+            for (j, row) in mds.iter().enumerate() {
+                let y = meta.query_advice(state[j], Rotation::next());
+                let mixed = row.iter().zip(sbox_out.iter()).fold(
+                    Expression::Constant(F::zero()),
+                    |acc, (coeff, sbox_lane)| {
+                        acc + meta.query_fixed(*coeff, Rotation::cur()) * sbox_lane.clone()
+                    },
+                );
+                constraints.push(s_full.clone() * (y - mixed));
+            }
+
+            constraints
+        });
+
+        // Partial round: the S-box is applied to lane 0 only; the other
+        // lanes only get the round constant added before the MDS mix.
+        meta.create_gate("poseidon partial round", |meta| {
+            let s_partial = meta.query_selector(s_partial);
+
+            let x0 = meta.query_advice(state[0], Rotation::cur())
+                + meta.query_fixed(round_constants[0], Rotation::cur());
+            let x2_0 = meta.query_advice(x2[0], Rotation::cur());
+            let x4_0 = meta.query_advice(x4[0], Rotation::cur());
+
+            let mut constraints = vec![
+                s_partial.clone() * (x2_0.clone() - x0.clone() * x0.clone()),
+                s_partial.clone() * (x4_0.clone() - x2_0.clone() * x2_0.clone()),
+            ];
+
+            let mut sbox_out = vec![x4_0 * x0];
+            for lane in state.iter().take(WIDTH).skip(1) {
+                sbox_out.push(meta.query_advice(*lane, Rotation::cur()));
+            }
+            // Lanes 1 and 2 only had the round constant added, on top of
+            // the queried advice value above.
+            for (lane, sbox_lane) in sbox_out.iter_mut().enumerate().skip(1) {
+                *sbox_lane =
+                    sbox_lane.clone() + meta.query_fixed(round_constants[lane], Rotation::cur());
+            }
+
+            for (j, row) in mds.iter().enumerate() {
+                let y = meta.query_advice(state[j], Rotation::next());
+                let mixed = row.iter().zip(sbox_out.iter()).fold(
+                    Expression::Constant(F::zero()),
+                    |acc, (coeff, sbox_lane)| {
+                        acc + meta.query_fixed(*coeff, Rotation::cur()) * sbox_lane.clone()
+                    },
+                );
+                constraints.push(s_partial.clone() * (y - mixed));
+            }
+
+            constraints
+        });
+
+        PoseidonConfig {
+            state,
+            x2,
+            x4,
+            round_constants,
+            mds,
+            s_full,
+            s_partial,
+        }
+    }
+
+    /// Runs the `R_F + R_P`-round permutation over `state`, whose cells
+    /// must already be assigned at `region` row `*offset`. Advances
+    /// `*offset` to the row holding the permuted state, and returns its
+    /// cells.
+    fn permute(
+        &self,
+        region: &mut Region<'_, F>,
+        offset: &mut usize,
+        mut state: [AssignedCell<F, F>; WIDTH],
+    ) -> Result<[AssignedCell<F, F>; WIDTH], Error> {
+        let config = self.config();
+        let round_constants = round_constants::<F>();
+        let mds = mds::<F>();
+        let full_rounds = R_F / 2;
+
+        for (round, rc) in round_constants.iter().enumerate() {
+            let is_full = round < full_rounds || round >= full_rounds + R_P;
+
+            for lane in 0..WIDTH {
+                region.assign_fixed(
+                    || "round constant",
+                    config.round_constants[lane],
+                    *offset,
+                    || Value::known(rc[lane]),
+                )?;
+            }
+            for (i, row) in config.mds.iter().enumerate() {
+                for (j, column) in row.iter().enumerate() {
+                    region.assign_fixed(|| "mds", *column, *offset, || Value::known(mds[i][j]))?;
+                }
+            }
+
+            let mut sbox_out: Vec<Value<F>> = Vec::with_capacity(WIDTH);
+            for lane in 0..WIDTH {
+                let x = state[lane].value().copied().map(|x| x + rc[lane]);
+                if is_full || lane == 0 {
+                    let x2 = x.map(|x| x.square());
+                    let x4 = x2.map(|x2| x2.square());
+                    region.assign_advice(|| "x2", config.x2[lane], *offset, || x2)?;
+                    region.assign_advice(|| "x4", config.x4[lane], *offset, || x4)?;
+                    sbox_out.push(x4 * x);
+                } else {
+                    region.assign_advice(
+                        || "x2",
+                        config.x2[lane],
+                        *offset,
+                        || Value::known(F::zero()),
+                    )?;
+                    region.assign_advice(
+                        || "x4",
+                        config.x4[lane],
+                        *offset,
+                        || Value::known(F::zero()),
+                    )?;
+                    sbox_out.push(x);
+                }
+            }
+
+            if is_full {
+                config.s_full.enable(region, *offset)?;
+            } else {
+                config.s_partial.enable(region, *offset)?;
+            }
+
+            let mut next_values = [Value::known(F::zero()); WIDTH];
+            for (j, row) in mds.iter().enumerate() {
+                next_values[j] = row
+                    .iter()
+                    .zip(sbox_out.iter())
+                    .fold(Value::known(F::zero()), |acc, (coeff, sbox_lane)| {
+                        acc + Value::known(*coeff) * *sbox_lane
+                    });
+            }
+
+            *offset += 1;
+            let mut next_cells = Vec::with_capacity(WIDTH);
+            for lane in 0..WIDTH {
+                next_cells.push(region.assign_advice(
+                    || "state",
+                    config.state[lane],
+                    *offset,
+                    || next_values[lane],
+                )?);
+            }
+            state = next_cells.try_into().expect("WIDTH cells expected");
+        }
+
+        Ok(state)
+    }
+}
+
+impl<F: FieldExt, V: Var<F>> PoseidonInstructions<F, V> for PoseidonChip<F> {
+    fn hash(&self, mut layouter: impl Layouter<F>, inputs: &[V]) -> Result<V, Error> {
+        let config = self.config();
+
+        layouter.assign_region(
+            || "poseidon",
+            |mut region| {
+                let mut offset = 0;
+                let mut chunks = inputs.chunks(RATE);
+
+                // The capacity lane (and any unused rate lane) is bound to
+                // the compile-time constant zero, the same way
+                // `load_constant` binds a cell to a fixed value; every lane
+                // actually absorbed is copy-constrained to its input cell.
+                let first_chunk = chunks.next().unwrap_or(&[]);
+                let mut state: [AssignedCell<F, F>; WIDTH] = (0..WIDTH)
+                    .map(|lane| -> Result<AssignedCell<F, F>, Error> {
+                        if let Some(input) = first_chunk.get(lane) {
+                            let cell = region.assign_advice(
+                                || "absorb",
+                                config.state[lane],
+                                offset,
+                                || input.value(),
+                            )?;
+                            region.constrain_equal(input.cell(), cell.cell())?;
+                            Ok(cell)
+                        } else {
+                            region.assign_advice_from_constant(
+                                || "capacity",
+                                config.state[lane],
+                                offset,
+                                F::zero(),
+                            )
+                        }
+                    })
+                    .collect::<Result<Vec<_>, Error>>()?
+                    .try_into()
+                    .expect("WIDTH cells expected");
+
+                state = self.permute(&mut region, &mut offset, state)?;
+
+                for chunk in chunks {
+                    offset += 1;
+                    let next_state: Vec<AssignedCell<F, F>> = (0..WIDTH)
+                        .map(|lane| -> Result<AssignedCell<F, F>, Error> {
+                            if let Some(input) = chunk.get(lane) {
+                                let cell = region.assign_advice(
+                                    || "absorb",
+                                    config.state[lane],
+                                    offset,
+                                    || input.value(),
+                                )?;
+                                region.constrain_equal(input.cell(), cell.cell())?;
+                                Ok(cell)
+                            } else {
+                                let cell = region.assign_advice(
+                                    || "carry",
+                                    config.state[lane],
+                                    offset,
+                                    || state[lane].value().copied(),
+                                )?;
+                                region.constrain_equal(state[lane].cell(), cell.cell())?;
+                                Ok(cell)
+                            }
+                        })
+                        .collect::<Result<Vec<_>, Error>>()?;
+
+                    state = next_state.try_into().expect("WIDTH cells expected");
+                    state = self.permute(&mut region, &mut offset, state)?;
+                }
+
+                Ok(V::from(state[0].clone()))
+            },
+        )
+    }
+}
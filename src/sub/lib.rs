@@ -0,0 +1,109 @@
+/// Constructs the Sub chip.
+use std::marker::PhantomData;
+
+use halo2_proofs::{
+    arithmetic::FieldExt,
+    circuit::{Chip, Layouter, Region},
+    plonk::{Advice, Column, ConstraintSystem, Error, Selector},
+    poly::Rotation,
+};
+
+use crate::utilities::lib::Var;
+
+/// Interface for the SubInstruction.
+pub(crate) trait SubInstructions<F: FieldExt, V: Var<F>>: Chip<F> {
+    /// Returns `c = a - b`.
+    fn sub(&self, layouter: impl Layouter<F>, a: V, b: V) -> Result<V, Error>;
+}
+
+/// Config for the sub chip.
+#[derive(Clone, Debug)]
+pub(crate) struct SubConfig {
+    /// One advice column for the instruction.
+    advice: Column<Advice>,
+    /// Selector for the sub instruction.
+    s_sub: Selector,
+}
+
+/// A chip for the sub functionality.
+pub(crate) struct SubChip<F: FieldExt> {
+    config: SubConfig,
+    _marker: PhantomData<F>,
+}
+
+// Implementations for the sub chip below.
+
+impl<F: FieldExt> Chip<F> for SubChip<F> {
+    type Config = SubConfig;
+    type Loaded = ();
+
+    fn config(&self) -> &Self::Config {
+        &self.config
+    }
+
+    fn loaded(&self) -> &Self::Loaded {
+        &()
+    }
+}
+
+impl<F: FieldExt> SubChip<F> {
+    pub(crate) fn construct(config: <Self as Chip<F>>::Config) -> Self {
+        Self {
+            config,
+            _marker: PhantomData,
+        }
+    }
+
+    pub(crate) fn configure(
+        meta: &mut ConstraintSystem<F>,
+        advice: Column<Advice>,
+    ) -> <Self as Chip<F>>::Config {
+        meta.enable_equality(advice);
+        let s_sub = meta.selector();
+
+        // Define our subtraction gate.
+        meta.create_gate("sub", |meta| {
+            // We want three advice cells and a selector cell.
+            // | a0  | s_sub |
+            // |-----|-------|
+            // | lhs | s_sub |
+            // | rhs |       |
+            // | out |       |
+            let lhs = meta.query_advice(advice, Rotation::cur());
+            let rhs = meta.query_advice(advice, Rotation::next());
+            let out = meta.query_advice(advice, Rotation(2));
+            let s_sub = meta.query_selector(s_sub);
+
+            // When s_sub = 0, any value is allowed in lhs, rhs, out.
+            // When s_sub != 0, lhs - rhs = out.
+            vec![s_sub * (lhs - rhs - out)]
+        });
+
+        SubConfig { advice, s_sub }
+    }
+}
+
+impl<F: FieldExt, V: Var<F>> SubInstructions<F, V> for SubChip<F> {
+    fn sub(&self, mut layouter: impl Layouter<F>, a: V, b: V) -> Result<V, Error> {
+        let config = self.config();
+
+        layouter.assign_region(
+            || "sub",
+            |mut region: Region<'_, F>| {
+                config.s_sub.enable(&mut region, 0)?;
+
+                let lhs = region.assign_advice(|| "lhs", config.advice, 0, || a.value())?;
+                region.constrain_equal(a.cell(), lhs.cell())?;
+
+                let rhs = region.assign_advice(|| "rhs", config.advice, 1, || b.value())?;
+                region.constrain_equal(b.cell(), rhs.cell())?;
+
+                let value = a.value() - b.value();
+
+                region
+                    .assign_advice(|| "lhs - rhs", config.advice, 2, || value)
+                    .map(V::from)
+            },
+        )
+    }
+}
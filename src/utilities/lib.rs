@@ -0,0 +1,45 @@
+/// Shared abstractions for decoupling chip gate logic from any single
+/// concrete witness representation.
+use halo2_proofs::{
+    arithmetic::FieldExt,
+    circuit::{AssignedCell, Cell, Layouter, Value},
+    plonk::{Advice, Column, Error},
+};
+
+/// A variable representing a value witnessed into the circuit.
+///
+/// Chips are generic over `V: Var<F>` rather than hardcoding a single
+/// concrete cell wrapper, so they can be reused by circuits that carry
+/// richer cell types (e.g. range-checked or tagged values).
+pub(crate) trait Var<F: FieldExt>: Clone + From<AssignedCell<F, F>> {
+    /// The cell at which this variable was allocated.
+    fn cell(&self) -> Cell;
+
+    /// The value of this variable.
+    fn value(&self) -> Value<F>;
+}
+
+/// Instructions shared by chips that witness private values into the
+/// circuit.
+pub(crate) trait UtilitiesInstructions<F: FieldExt> {
+    /// Variable representing a number.
+    type Var: Var<F>;
+
+    /// Loads a number into the circuit as a private input, in the given
+    /// advice column.
+    fn load_private(
+        &self,
+        mut layouter: impl Layouter<F>,
+        column: Column<Advice>,
+        value: Value<F>,
+    ) -> Result<Self::Var, Error> {
+        layouter.assign_region(
+            || "load private",
+            |mut region| {
+                region
+                    .assign_advice(|| "private input", column, 0, || value)
+                    .map(Self::Var::from)
+            },
+        )
+    }
+}